@@ -1,7 +1,9 @@
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use crate::container::ContainerManager;
-use crate::storage::Storage;
+use crate::limits::ResourceLimits;
+use crate::storage::{ContainerConfig, Storage};
+use crate::volume::{VolumeManager, VolumeMount};
 
 #[derive(Parser)]
 #[command(name = "dock")]
@@ -9,16 +11,40 @@ use crate::storage::Storage;
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
+
+    /// Output format for `list`, `inspect`, and `logs` (`logs --follow` always streams plain text)
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Table)]
+    pub format: OutputFormat,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Table,
+    Json,
 }
 
 #[derive(Subcommand)]
 pub enum Commands {
+    /// Pull a base rootfs image into the local cache
+    Pull {
+        /// Image name (e.g. alpine, python3)
+        image: String,
+        /// Re-fetch even if the image is already cached
+        #[arg(long)]
+        force: bool,
+    },
     /// Create a new container
     Create {
         /// Container name
         name: String,
         /// Path to Python script
         script: String,
+        /// Base rootfs image to provision the container with
+        #[arg(long, default_value = "python3")]
+        image: String,
+        /// Re-fetch the image even if it is already cached
+        #[arg(long)]
+        pull: bool,
     },
     /// Start a container
     Start {
@@ -27,6 +53,30 @@ pub enum Commands {
         /// Port mapping (host:container)
         #[arg(short, long)]
         port: Option<String>,
+        /// Re-fetch the container's image before starting
+        #[arg(long)]
+        pull: bool,
+        /// Attach a named volume (name:/path), repeatable
+        #[arg(long = "volume")]
+        volumes: Vec<String>,
+        /// Set an environment variable (KEY=VALUE), repeatable
+        #[arg(short = 'e', long = "env")]
+        env: Vec<String>,
+        /// Load environment variables from a file (KEY=VALUE per line)
+        #[arg(long)]
+        env_file: Option<String>,
+        /// Bind a host directory into the container (host:container), repeatable
+        #[arg(long = "bind")]
+        binds: Vec<String>,
+        /// Memory limit (e.g. 512m, 1g)
+        #[arg(long)]
+        memory: Option<String>,
+        /// CPU limit in cores, e.g. 1.5
+        #[arg(long)]
+        cpus: Option<f64>,
+        /// Maximum number of processes/threads
+        #[arg(long = "pids-limit")]
+        pids_limit: Option<u32>,
     },
     /// Stop a container
     Stop {
@@ -35,6 +85,11 @@ pub enum Commands {
     },
     /// List all containers
     List,
+    /// Show detailed status for a container
+    Inspect {
+        /// Container name
+        name: String,
+    },
     /// Enter a container shell
     Enter {
         /// Container name
@@ -44,6 +99,17 @@ pub enum Commands {
     Logs {
         /// Container name
         name: String,
+        /// Keep streaming new log output as it's written
+        #[arg(short, long)]
+        follow: bool,
+    },
+    /// Run a one-off command inside a running container
+    Exec {
+        /// Container name
+        name: String,
+        /// Command (and arguments) to run
+        #[arg(trailing_var_arg = true, required = true)]
+        command: Vec<String>,
     },
     /// Remove a container
     Remove {
@@ -52,6 +118,44 @@ pub enum Commands {
     },
     /// Update dock from git
     Update,
+    /// Create and start every service in a compose manifest
+    Up {
+        /// Path to the compose manifest
+        #[arg(short, long, default_value = "dock-compose.yml")]
+        file: String,
+    },
+    /// Stop (and optionally remove) every service in a compose manifest
+    Down {
+        /// Path to the compose manifest
+        #[arg(short, long, default_value = "dock-compose.yml")]
+        file: String,
+        /// Also remove each container after stopping it
+        #[arg(long)]
+        remove: bool,
+    },
+    /// Manage named persistent volumes
+    Volume {
+        #[command(subcommand)]
+        action: VolumeCommands,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum VolumeCommands {
+    /// Create a new volume
+    Create {
+        /// Volume name
+        name: String,
+    },
+    /// List all volumes
+    List,
+    /// Remove a volume
+    Remove {
+        /// Volume name
+        name: String,
+    },
+    /// Remove volumes not referenced by any container
+    Prune,
 }
 
 impl Cli {
@@ -60,16 +164,188 @@ impl Cli {
         let manager = ContainerManager::new(storage);
 
         match self.command {
-            Commands::Create { name, script } => manager.create(&name, &script).await?,
-            Commands::Start { name, port } => manager.start(&name, port).await?,
+            Commands::Pull { image, force } => manager.pull(&image, force).await?,
+            Commands::Create {
+                name,
+                script,
+                image,
+                pull,
+            } => manager.create(&name, &script, &image, pull, None).await?,
+            Commands::Start {
+                name,
+                port,
+                pull,
+                volumes,
+                env,
+                env_file,
+                binds,
+                memory,
+                cpus,
+                pids_limit,
+            } => {
+                let volumes = volumes
+                    .iter()
+                    .map(|v| VolumeMount::parse(v))
+                    .collect::<Result<Vec<_>>>()?;
+
+                let mut env_pairs = Vec::new();
+                if let Some(path) = env_file {
+                    env_pairs.extend(parse_env_file(&path)?);
+                }
+                for pair in &env {
+                    env_pairs.push(parse_env_pair(pair)?);
+                }
+
+                let limits = ResourceLimits {
+                    memory,
+                    cpus,
+                    pids_limit,
+                };
+
+                manager
+                    .start(&name, port, pull, volumes, env_pairs, binds, limits)
+                    .await?
+            }
             Commands::Stop { name } => manager.stop(&name).await?,
-            Commands::List => manager.list().await?,
+            Commands::List => {
+                let containers = manager.list().await?;
+                print_containers(&containers, self.format);
+            }
+            Commands::Inspect { name } => {
+                let config = manager.inspect(&name).await?;
+                print_inspect(&config, self.format);
+            }
             Commands::Enter { name } => manager.enter(&name).await?,
-            Commands::Logs { name } => manager.logs(&name).await?,
+            Commands::Logs { name, follow } => {
+                if follow {
+                    manager.follow_logs(&name).await?;
+                } else {
+                    let content = manager.logs(&name).await?;
+                    print_logs(&name, content.as_deref(), self.format);
+                }
+            }
+            Commands::Exec { name, command } => {
+                let code = manager.exec(&name, &command).await?;
+                std::process::exit(code);
+            }
             Commands::Remove { name } => manager.remove(&name).await?,
             Commands::Update => manager.update().await?,
+            Commands::Up { file } => manager.up(&file).await?,
+            Commands::Down { file, remove } => manager.down(&file, remove).await?,
+            Commands::Volume { action } => {
+                let volume_manager = VolumeManager::new()?;
+                match action {
+                    VolumeCommands::Create { name } => volume_manager.create(&name).await?,
+                    VolumeCommands::List => volume_manager.list().await?,
+                    VolumeCommands::Remove { name } => volume_manager.remove(&name).await?,
+                    VolumeCommands::Prune => volume_manager.prune(&Storage::new()?).await?,
+                }
+            }
         }
 
         Ok(())
     }
 }
+
+fn print_containers(containers: &[ContainerConfig], format: OutputFormat) {
+    if format == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(containers).unwrap());
+        return;
+    }
+
+    if containers.is_empty() {
+        println!("No containers found");
+        return;
+    }
+
+    println!(
+        "{:<20} {:<15} {:<20} {:<15}",
+        "NAME", "STATUS", "PYTHON", "PORT"
+    );
+    println!("{}", "-".repeat(70));
+
+    for config in containers {
+        let port = config.port_mapping.as_deref().unwrap_or("-");
+        println!(
+            "{:<20} {:<15} {:<20} {:<15}",
+            config.name, config.status, config.python_version, port
+        );
+    }
+}
+
+fn print_inspect(config: &ContainerConfig, format: OutputFormat) {
+    if format == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(config).unwrap());
+        return;
+    }
+
+    println!("Name:       {}", config.name);
+    println!("Status:     {}", config.status);
+    println!(
+        "PID:        {}",
+        config.pid.map(|p| p.to_string()).unwrap_or_else(|| "-".to_string())
+    );
+    println!(
+        "Exit Code:  {}",
+        config
+            .exit_code
+            .map(|c| c.to_string())
+            .unwrap_or_else(|| "-".to_string())
+    );
+    println!("Image:      {}", config.image);
+    println!(
+        "Port:       {}",
+        config.port_mapping.as_deref().unwrap_or("-")
+    );
+    println!(
+        "Memory:     {}",
+        config.limits.memory.as_deref().unwrap_or("-")
+    );
+    println!(
+        "CPUs:       {}",
+        config
+            .limits
+            .cpus
+            .map(|c| c.to_string())
+            .unwrap_or_else(|| "-".to_string())
+    );
+    println!(
+        "Pids Limit: {}",
+        config
+            .limits
+            .pids_limit
+            .map(|p| p.to_string())
+            .unwrap_or_else(|| "-".to_string())
+    );
+}
+
+fn print_logs(name: &str, content: Option<&str>, format: OutputFormat) {
+    if format == OutputFormat::Json {
+        let payload = serde_json::json!({ "name": name, "content": content });
+        println!("{}", serde_json::to_string_pretty(&payload).unwrap());
+        return;
+    }
+
+    match content {
+        Some(content) => println!("{}", content),
+        None => println!("No logs available for container '{}'", name),
+    }
+}
+
+pub(crate) fn parse_env_pair(spec: &str) -> Result<(String, String)> {
+    spec.split_once('=')
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .ok_or_else(|| anyhow::anyhow!("invalid env spec '{}', expected KEY=VALUE", spec))
+}
+
+fn parse_env_file(path: &str) -> Result<Vec<(String, String)>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("failed to read env file '{}': {}", path, e))?;
+
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(parse_env_pair)
+        .collect()
+}
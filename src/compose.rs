@@ -0,0 +1,140 @@
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use std::collections::{HashMap, VecDeque};
+
+/// A `dock-compose.yml` manifest describing several related containers.
+#[derive(Debug, Deserialize)]
+pub struct ComposeManifest {
+    pub services: HashMap<String, ServiceSpec>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ServiceSpec {
+    pub script: String,
+    #[serde(default)]
+    pub image: Option<String>,
+    /// Overrides the shebang-detected interpreter, e.g. when a script's
+    /// shebang doesn't reflect how it should actually be run.
+    #[serde(default)]
+    pub python_version: Option<String>,
+    #[serde(default)]
+    pub port: Option<String>,
+    #[serde(default)]
+    pub env: Vec<String>,
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+}
+
+impl ComposeManifest {
+    pub fn load(path: &str) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("failed to read compose file '{}': {}", path, e))?;
+        let manifest: ComposeManifest = serde_yaml::from_str(&content)
+            .map_err(|e| anyhow!("failed to parse compose file '{}': {}", path, e))?;
+        Ok(manifest)
+    }
+
+    /// Orders services so each one comes after everything in its
+    /// `depends_on`, via a standard Kahn's-algorithm topological sort.
+    pub fn start_order(&self) -> Result<Vec<String>> {
+        let mut in_degree: HashMap<&str, usize> =
+            self.services.keys().map(|k| (k.as_str(), 0)).collect();
+        let mut dependents: HashMap<&str, Vec<&str>> = HashMap::new();
+
+        for (name, spec) in &self.services {
+            for dep in &spec.depends_on {
+                if !self.services.contains_key(dep) {
+                    return Err(anyhow!(
+                        "service '{}' depends_on unknown service '{}'",
+                        name,
+                        dep
+                    ));
+                }
+                *in_degree.get_mut(name.as_str()).unwrap() += 1;
+                dependents.entry(dep.as_str()).or_default().push(name.as_str());
+            }
+        }
+
+        let mut queue: VecDeque<&str> = in_degree
+            .iter()
+            .filter(|(_, &degree)| degree == 0)
+            .map(|(name, _)| *name)
+            .collect();
+        let mut order = Vec::new();
+
+        while let Some(name) = queue.pop_front() {
+            order.push(name.to_string());
+            if let Some(deps) = dependents.get(name) {
+                for &dependent in deps {
+                    let entry = in_degree.get_mut(dependent).unwrap();
+                    *entry -= 1;
+                    if *entry == 0 {
+                        queue.push_back(dependent);
+                    }
+                }
+            }
+        }
+
+        if order.len() != self.services.len() {
+            return Err(anyhow!("dependency cycle detected in compose manifest"));
+        }
+
+        Ok(order)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service(depends_on: &[&str]) -> ServiceSpec {
+        ServiceSpec {
+            script: "app.py".to_string(),
+            image: None,
+            python_version: None,
+            port: None,
+            env: Vec::new(),
+            depends_on: depends_on.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    fn manifest(services: &[(&str, &[&str])]) -> ComposeManifest {
+        ComposeManifest {
+            services: services
+                .iter()
+                .map(|(name, deps)| (name.to_string(), service(deps)))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn orders_independent_services_in_some_order() {
+        let manifest = manifest(&[("a", &[]), ("b", &[])]);
+        let mut order = manifest.start_order().unwrap();
+        order.sort();
+        assert_eq!(order, vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn orders_dependents_after_dependencies() {
+        let manifest = manifest(&[("db", &[]), ("web", &["db"]), ("worker", &["db", "web"])]);
+        let order = manifest.start_order().unwrap();
+
+        let pos = |name: &str| order.iter().position(|n| n == name).unwrap();
+        assert!(pos("db") < pos("web"));
+        assert!(pos("web") < pos("worker"));
+    }
+
+    #[test]
+    fn rejects_unknown_dependency() {
+        let manifest = manifest(&[("web", &["missing"])]);
+        assert!(manifest.start_order().is_err());
+    }
+
+    #[test]
+    fn rejects_dependency_cycle() {
+        let manifest = manifest(&[("a", &["b"]), ("b", &["a"])]);
+        let err = manifest.start_order().unwrap_err();
+        assert!(err.to_string().contains("cycle"));
+    }
+}
@@ -1,21 +1,84 @@
 use anyhow::{anyhow, Result};
 use std::fs;
+use std::os::unix::process::CommandExt;
 use std::process::Command;
+use std::time::Duration;
 use uuid::Uuid;
 
-use crate::python::detect_python_version;
+use crate::compose::ComposeManifest;
+use crate::limits::{apply_rlimits, compute_rlimits, CgroupScope, ResourceLimits};
+use crate::python::{detect_python_version, PythonVersion};
+use crate::rootfs::RootfsStore;
 use crate::storage::{ContainerConfig, Storage};
+use crate::volume::{VolumeMount, VolumeStore};
 
 pub struct ContainerManager {
     storage: Storage,
 }
 
+/// Checks whether a process is still alive via `kill(pid, 0)`, per the
+/// standard POSIX liveness-check idiom (sends no signal, just probes).
+fn process_alive(pid: u32) -> bool {
+    // A value that doesn't fit a positive pid_t can't be a live process, and
+    // casting it would turn kill() into a probe of a process group or of
+    // every process (kill(-1, 0)).
+    match libc::pid_t::try_from(pid) {
+        Ok(pid) if pid > 0 => unsafe { libc::kill(pid, 0) == 0 },
+        _ => false,
+    }
+}
+
 impl ContainerManager {
     pub fn new(storage: Storage) -> Self {
         ContainerManager { storage }
     }
 
-    pub async fn create(&self, name: &str, script: &str) -> Result<()> {
+    /// Reconciles a container's recorded status against reality: if it's
+    /// marked "running" but its PID is gone, flips it to "exited" and
+    /// records the exit code the wrapper shell captured for us.
+    fn reconcile(&self, config: &mut ContainerConfig) -> Result<()> {
+        if config.status != "running" {
+            return Ok(());
+        }
+
+        let Some(pid) = config.pid else {
+            return Ok(());
+        };
+
+        if process_alive(pid) {
+            return Ok(());
+        }
+
+        config.status = "exited".to_string();
+        config.exit_code = self.read_exit_code(&config.name);
+        config.pid = None;
+        self.storage.save_config(config)?;
+        Ok(())
+    }
+
+    fn read_exit_code(&self, name: &str) -> Option<i32> {
+        fs::read_to_string(self.storage.exit_code_path(name))
+            .ok()?
+            .trim()
+            .parse()
+            .ok()
+    }
+
+    pub async fn pull(&self, image: &str, force: bool) -> Result<()> {
+        let rootfs_store = RootfsStore::new()?;
+        let resolved = rootfs_store.pull(image, force)?;
+        println!("✓ Pulled image '{}' ({})", resolved.name, resolved.digest);
+        Ok(())
+    }
+
+    pub async fn create(
+        &self,
+        name: &str,
+        script: &str,
+        image: &str,
+        pull: bool,
+        python_version_override: Option<&str>,
+    ) -> Result<()> {
         if self.storage.container_exists(name) {
             return Err(anyhow!("Container '{}' already exists", name));
         }
@@ -24,9 +87,15 @@ impl ContainerManager {
             return Err(anyhow!("Script '{}' not found", script));
         }
 
-        let python_version = detect_python_version(script)?;
+        let python_version = match python_version_override {
+            Some(value) => PythonVersion::parse(value)?,
+            None => detect_python_version(script)?,
+        };
         let id = Uuid::new_v4().to_string();
 
+        let rootfs_store = RootfsStore::new()?;
+        let resolved = rootfs_store.pull(image, pull)?;
+
         let config = ContainerConfig {
             id,
             name: name.to_string(),
@@ -34,27 +103,67 @@ impl ContainerManager {
             python_version: format!("{:?}", python_version),
             status: "stopped".to_string(),
             port_mapping: None,
+            image: image.to_string(),
+            image_digest: Some(resolved.digest),
+            pid: None,
+            exit_code: None,
+            volumes: Vec::new(),
+            env: Vec::new(),
+            limits: ResourceLimits::default(),
         };
 
         self.storage.save_config(&config)?;
 
         let fs_path = self.storage.filesystem_path(name);
-        fs::create_dir_all(&fs_path)?;
+        rootfs_store.materialize(image, &fs_path)?;
 
         println!(
-            "✓ Container '{}' created (Python: {:?})",
-            name, python_version
+            "✓ Container '{}' created (Python: {:?}, image: {})",
+            name, python_version, image
         );
         Ok(())
     }
 
-    pub async fn start(&self, name: &str, port: Option<String>) -> Result<()> {
+    pub async fn start(
+        &self,
+        name: &str,
+        port: Option<String>,
+        pull: bool,
+        volumes: Vec<VolumeMount>,
+        env: Vec<(String, String)>,
+        host_binds: Vec<String>,
+        limits: ResourceLimits,
+    ) -> Result<()> {
         let mut config = self.storage.load_config(name)?;
+        self.reconcile(&mut config)?;
 
         if config.status == "running" {
             return Err(anyhow!("Container '{}' is already running", name));
         }
 
+        if !volumes.is_empty() {
+            config.volumes = volumes;
+        }
+
+        if !env.is_empty() {
+            config.env = env;
+        }
+
+        if !limits.is_empty() {
+            config.limits = limits;
+        }
+
+        let volume_store = VolumeStore::new()?;
+        for mount in &config.volumes {
+            if !volume_store.exists(&mount.name) {
+                return Err(anyhow!(
+                    "Volume '{}' not found. Run `dock volume create {}` first.",
+                    mount.name,
+                    mount.name
+                ));
+            }
+        }
+
         let script = &config.script;
         if !std::path::Path::new(script).exists() {
             return Err(anyhow!(
@@ -63,9 +172,23 @@ impl ContainerManager {
             ));
         }
 
-        config.status = "running".to_string();
-        config.port_mapping = port.clone();
-        self.storage.save_config(&config)?;
+        let fs_path = self.storage.filesystem_path(name);
+        let rootfs_store = RootfsStore::new()?;
+
+        if pull {
+            let resolved = rootfs_store.pull(&config.image, true)?;
+            rootfs_store.materialize(&config.image, &fs_path)?;
+            config.image_digest = Some(resolved.digest);
+        }
+
+        if !fs_path.exists() || fs::read_dir(&fs_path)?.next().is_none() {
+            return Err(anyhow!(
+                "Container '{}' has no rootfs provisioned. Run `dock start {} --pull` to provision it from image '{}'.",
+                name,
+                name,
+                config.image
+            ));
+        }
 
         let python_cmd = match config.python_version.as_str() {
             "Python2" => "python2",
@@ -75,22 +198,93 @@ impl ContainerManager {
 
         let logs_path = self.storage.logs_path(name);
         let log_file = fs::File::create(&logs_path)?;
-
-        let mut cmd = Command::new("proot");
-        cmd.arg("-r")
-            .arg(self.storage.filesystem_path(name))
-            .arg(python_cmd)
-            .arg(script);
+        let exit_code_path = self.storage.exit_code_path(name);
+        // Clear the previous run's exit code so it can't be recorded for this run.
+        let _ = fs::remove_file(&exit_code_path);
+
+        let mut bind_specs: Vec<String> = config
+            .volumes
+            .iter()
+            .map(|mount| format!("{}:{}", volume_store.data_path(&mount.name).display(), mount.target))
+            .collect();
+        bind_specs.extend(host_binds.iter().cloned());
+
+        let mut proot_args: Vec<String> = vec!["-r".to_string(), fs_path.display().to_string()];
+        for spec in &bind_specs {
+            proot_args.push("-b".to_string());
+            proot_args.push(spec.clone());
+        }
+        proot_args.push(python_cmd.to_string());
+        proot_args.push(script.to_string());
+
+        // Wrap proot in a shell so we can capture its exit code after the
+        // fact, since a later `dock` invocation isn't this process's parent
+        // and can't reap it directly. The proot argv is passed as separate
+        // positional parameters and re-expanded via "$@" rather than
+        // interpolated into the script text, so spaces or shell
+        // metacharacters in a bind path, script path, or container name
+        // can't be (mis)interpreted by the shell.
+        const EXIT_WRAPPER: &str = "\"$@\"; echo $? > \"$DOCK_EXIT_FILE\"";
+
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c")
+            .arg(EXIT_WRAPPER)
+            .arg("dock-proot")
+            .arg("proot")
+            .args(&proot_args);
+        cmd.env("DOCK_EXIT_FILE", exit_code_path.display().to_string());
+        // New process group so stop() can signal proot's children too.
+        cmd.process_group(0);
 
         if let Some(port_map) = &port {
             cmd.env("DOCK_PORT_MAP", port_map);
         }
 
+        for (key, value) in &config.env {
+            cmd.env(key, value);
+        }
+
         cmd.env("DOCK_CONTAINER", name);
 
+        // Created before spawn (it only needs the name, not a live PID) so
+        // we know whether pids containment can ride the cgroup's `pids`
+        // controller before deciding whether rlimits need the RLIMIT_NPROC
+        // fallback.
+        let cgroup = CgroupScope::create(name, &config.limits);
+        if config.limits.pids_limit.is_some() && cgroup.is_none() {
+            eprintln!(
+                "Warning: no writable cgroup v2 hierarchy for container '{}'; falling back to RLIMIT_NPROC, which caps process count for the whole Termux user rather than just this container",
+                name
+            );
+        }
+
+        let computed_rlimits = compute_rlimits(&config.limits, cgroup.is_some())?;
+        unsafe {
+            cmd.pre_exec(move || apply_rlimits(&computed_rlimits));
+        }
+
         let child = cmd.stdout(log_file.try_clone()?).stderr(log_file).spawn()?;
+        let pid = child.id();
+
+        if let Some(cgroup) = cgroup {
+            // The process is already live; an unwritable/partially-delegated
+            // cgroup tree (common on Termux) shouldn't orphan it, so just
+            // fall back to the rlimits we already applied.
+            if let Err(e) = cgroup.add_process(pid) {
+                eprintln!(
+                    "Warning: could not move container '{}' into its cgroup scope: {}",
+                    name, e
+                );
+            }
+        }
+
+        config.status = "running".to_string();
+        config.port_mapping = port.clone();
+        config.pid = Some(pid);
+        config.exit_code = None;
+        self.storage.save_config(&config)?;
 
-        println!("✓ Container '{}' started (PID: {})", name, child.id());
+        println!("✓ Container '{}' started (PID: {})", name, pid);
         if let Some(p) = port {
             println!("  Port mapping: {}", p);
         }
@@ -100,46 +294,54 @@ impl ContainerManager {
 
     pub async fn stop(&self, name: &str) -> Result<()> {
         let mut config = self.storage.load_config(name)?;
+        self.reconcile(&mut config)?;
 
-        if config.status == "stopped" {
+        if config.status != "running" {
             return Err(anyhow!("Container '{}' is already stopped", name));
         }
 
+        let pid = config
+            .pid
+            .ok_or_else(|| anyhow!("Container '{}' has no recorded PID", name))?;
+
+        unsafe {
+            libc::kill(-(pid as libc::pid_t), libc::SIGTERM);
+        }
+        std::thread::sleep(Duration::from_millis(500));
+        if process_alive(pid) {
+            unsafe {
+                libc::kill(-(pid as libc::pid_t), libc::SIGKILL);
+            }
+        }
+
         config.status = "stopped".to_string();
+        config.pid = None;
+        config.exit_code = self.read_exit_code(name);
         self.storage.save_config(&config)?;
 
-        Command::new("pkill")
-            .arg("-f")
-            .arg(format!("DOCK_CONTAINER={}", name))
-            .output()?;
+        crate::limits::remove_scope(name);
 
         println!("✓ Container '{}' stopped", name);
         Ok(())
     }
 
-    pub async fn list(&self) -> Result<()> {
-        let containers = self.storage.list_containers()?;
-
-        if containers.is_empty() {
-            println!("No containers found");
-            return Ok(());
-        }
+    /// Returns the container's true, reconciled status. Callers (the CLI's
+    /// human table, JSON output, or an embedding tool) decide how to present it.
+    pub async fn inspect(&self, name: &str) -> Result<ContainerConfig> {
+        let mut config = self.storage.load_config(name)?;
+        self.reconcile(&mut config)?;
+        Ok(config)
+    }
 
-        println!(
-            "{:<20} {:<15} {:<20} {:<15}",
-            "NAME", "STATUS", "PYTHON", "PORT"
-        );
-        println!("{}", "-".repeat(70));
+    /// Returns every container's true, reconciled status.
+    pub async fn list(&self) -> Result<Vec<ContainerConfig>> {
+        let mut containers = self.storage.list_containers()?;
 
-        for config in containers {
-            let port = config.port_mapping.unwrap_or_else(|| "-".to_string());
-            println!(
-                "{:<20} {:<15} {:<20} {:<15}",
-                config.name, config.status, config.python_version, port
-            );
+        for config in containers.iter_mut() {
+            self.reconcile(config)?;
         }
 
-        Ok(())
+        Ok(containers)
     }
 
     pub async fn enter(&self, name: &str) -> Result<()> {
@@ -166,7 +368,21 @@ impl ContainerManager {
         Ok(())
     }
 
-    pub async fn logs(&self, name: &str) -> Result<()> {
+    /// Returns the container's captured log output, or `None` if it has no
+    /// log file yet (it has never been started).
+    pub async fn logs(&self, name: &str) -> Result<Option<String>> {
+        let logs_path = self.storage.logs_path(name);
+
+        if !logs_path.exists() {
+            return Ok(None);
+        }
+
+        Ok(Some(fs::read_to_string(logs_path)?))
+    }
+
+    /// Streams new log lines as they're written. This is inherently a live,
+    /// textual side effect, so it prints directly rather than returning data.
+    pub async fn follow_logs(&self, name: &str) -> Result<()> {
         let logs_path = self.storage.logs_path(name);
 
         if !logs_path.exists() {
@@ -174,13 +390,74 @@ impl ContainerManager {
             return Ok(());
         }
 
-        let content = fs::read_to_string(logs_path)?;
-        println!("{}", content);
-        Ok(())
+        use std::io::{BufRead, BufReader, Seek, SeekFrom};
+
+        let mut file = fs::File::open(&logs_path)?;
+        let mut offset = file.seek(SeekFrom::End(0))?;
+        let mut reader = BufReader::new(file);
+        let mut line = String::new();
+
+        loop {
+            // `start()` truncates the log file on every restart, so a
+            // shrunk file means the run we were following has restarted
+            // out from under us — reseek to the top to pick up its output.
+            let len = reader.get_ref().metadata()?.len();
+            if len < offset {
+                reader.get_mut().seek(SeekFrom::Start(0))?;
+                offset = 0;
+            }
+
+            line.clear();
+            let read = reader.read_line(&mut line)?;
+            if read == 0 {
+                std::thread::sleep(Duration::from_millis(200));
+                continue;
+            }
+            offset += read as u64;
+            print!("{}", line);
+        }
+    }
+
+    /// Runs an arbitrary one-off command inside a running container's proot
+    /// root, returning the command's exit status.
+    pub async fn exec(&self, name: &str, command: &[String]) -> Result<i32> {
+        let mut config = self.storage.load_config(name)?;
+        self.reconcile(&mut config)?;
+
+        if config.status != "running" {
+            return Err(anyhow!("Container '{}' is not running", name));
+        }
+
+        if command.is_empty() {
+            return Err(anyhow!("No command given to exec"));
+        }
+
+        let fs_path = self.storage.filesystem_path(name);
+        let volume_store = VolumeStore::new()?;
+
+        let mut cmd = Command::new("proot");
+        cmd.arg("-r").arg(&fs_path);
+        for mount in &config.volumes {
+            cmd.arg("-b").arg(format!(
+                "{}:{}",
+                volume_store.data_path(&mount.name).display(),
+                mount.target
+            ));
+        }
+        cmd.args(command);
+
+        for (key, value) in &config.env {
+            cmd.env(key, value);
+        }
+        cmd.env("DOCK_CONTAINER", name);
+
+        let status = cmd.status()?;
+        Ok(status.code().unwrap_or(-1))
     }
 
     pub async fn remove(&self, name: &str) -> Result<()> {
-        let config = self.storage.load_config(name)?;
+        let mut config = self.storage.load_config(name)?;
+        self.reconcile(&mut config)?;
 
         if config.status == "running" {
             return Err(anyhow!(
@@ -190,6 +467,7 @@ impl ContainerManager {
         }
 
         self.storage.delete_container(name)?;
+        crate::limits::remove_scope(name);
         println!("✓ Container '{}' removed", name);
         Ok(())
     }
@@ -235,4 +513,150 @@ impl ContainerManager {
 
         Ok(())
     }
+
+    pub async fn up(&self, file: &str) -> Result<()> {
+        let manifest = ComposeManifest::load(file)?;
+        let order = manifest.start_order()?;
+
+        let mut started = Vec::new();
+        for name in order {
+            let spec = &manifest.services[&name];
+            let image = spec.image.clone().unwrap_or_else(|| "python3".to_string());
+
+            if !self.storage.container_exists(&name) {
+                if let Err(e) = self
+                    .create(
+                        &name,
+                        &spec.script,
+                        &image,
+                        false,
+                        spec.python_version.as_deref(),
+                    )
+                    .await
+                {
+                    self.rollback(&started).await;
+                    return Err(e);
+                }
+            }
+
+            let env = spec
+                .env
+                .iter()
+                .map(|pair| crate::cli::parse_env_pair(pair))
+                .collect::<Result<Vec<_>>>()?;
+
+            if let Err(e) = self
+                .start(
+                    &name,
+                    spec.port.clone(),
+                    false,
+                    Vec::new(),
+                    env,
+                    Vec::new(),
+                    ResourceLimits::default(),
+                )
+                .await
+            {
+                self.rollback(&started).await;
+                return Err(e);
+            }
+
+            started.push(name);
+        }
+
+        println!("✓ Brought up {} service(s) from '{}'", started.len(), file);
+        Ok(())
+    }
+
+    async fn rollback(&self, started: &[String]) {
+        for name in started.iter().rev() {
+            let _ = self.stop(name).await;
+        }
+    }
+
+    pub async fn down(&self, file: &str, remove: bool) -> Result<()> {
+        let manifest = ComposeManifest::load(file)?;
+        let mut order = manifest.start_order()?;
+        order.reverse();
+
+        for name in &order {
+            if !self.storage.container_exists(name) {
+                continue;
+            }
+            let _ = self.stop(name).await;
+            if remove {
+                self.remove(name).await?;
+            }
+        }
+
+        println!("✓ Brought down {} service(s) from '{}'", order.len(), file);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::limits::ResourceLimits;
+
+    fn manager(test_name: &str) -> ContainerManager {
+        let base_dir = std::env::temp_dir().join(format!(
+            "dock_container_test_{}_{}",
+            std::process::id(),
+            test_name
+        ));
+        let storage = Storage::with_base_dir(base_dir).unwrap();
+        ContainerManager::new(storage)
+    }
+
+    fn running_config(pid: u32) -> ContainerConfig {
+        ContainerConfig {
+            id: "id".to_string(),
+            name: "test".to_string(),
+            script: "app.py".to_string(),
+            python_version: "Python3".to_string(),
+            status: "running".to_string(),
+            port_mapping: None,
+            image: "python3".to_string(),
+            image_digest: None,
+            pid: Some(pid),
+            exit_code: None,
+            volumes: Vec::new(),
+            env: Vec::new(),
+            limits: ResourceLimits::default(),
+        }
+    }
+
+    #[test]
+    fn reconcile_leaves_non_running_status_untouched() {
+        let manager = manager("non_running");
+        let mut config = running_config(u32::MAX);
+        config.status = "stopped".to_string();
+
+        manager.reconcile(&mut config).unwrap();
+
+        assert_eq!(config.status, "stopped");
+    }
+
+    #[test]
+    fn reconcile_leaves_running_status_when_pid_is_alive() {
+        let manager = manager("alive_pid");
+        let mut config = running_config(std::process::id());
+
+        manager.reconcile(&mut config).unwrap();
+
+        assert_eq!(config.status, "running");
+        assert_eq!(config.pid, Some(std::process::id()));
+    }
+
+    #[test]
+    fn reconcile_marks_exited_when_pid_is_gone() {
+        let manager = manager("dead_pid");
+        let mut config = running_config(u32::MAX);
+
+        manager.reconcile(&mut config).unwrap();
+
+        assert_eq!(config.status, "exited");
+        assert_eq!(config.pid, None);
+    }
 }
@@ -0,0 +1,205 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Optional resource caps for a container, applied at `start` time since
+/// proot itself provides no isolation of its own.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResourceLimits {
+    /// e.g. "512m", "1g"
+    pub memory: Option<String>,
+    /// Fractional CPU count, e.g. 1.5
+    pub cpus: Option<f64>,
+    /// Caps this container's own process/thread count via the cgroup v2
+    /// `pids` controller when available (see `CgroupScope::create`), since
+    /// `RLIMIT_NPROC` is keyed to the whole Termux UID rather than to this
+    /// container and is only used as a fallback.
+    pub pids_limit: Option<u32>,
+}
+
+impl ResourceLimits {
+    pub fn is_empty(&self) -> bool {
+        self.memory.is_none() && self.cpus.is_none() && self.pids_limit.is_none()
+    }
+}
+
+pub fn parse_memory_bytes(spec: &str) -> Result<u64> {
+    let lower = spec.trim().to_lowercase();
+    let (digits, multiplier) = if let Some(n) = lower.strip_suffix('g') {
+        (n, 1024 * 1024 * 1024)
+    } else if let Some(n) = lower.strip_suffix('m') {
+        (n, 1024 * 1024)
+    } else if let Some(n) = lower.strip_suffix('k') {
+        (n, 1024)
+    } else {
+        (lower.as_str(), 1)
+    };
+
+    digits
+        .parse::<u64>()
+        .map(|value| value * multiplier)
+        .map_err(|_| anyhow!("invalid memory limit '{}', expected e.g. 512m or 1g", spec))
+}
+
+/// The raw integer values `apply_rlimits` needs, computed up front in the
+/// parent so the `pre_exec` closure never has to parse or allocate.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ComputedRlimits {
+    memory_bytes: Option<u64>,
+    pids: Option<u32>,
+}
+
+/// Parses/validates a `ResourceLimits` into plain integers, in the parent
+/// process — the only place it's safe to do string parsing or allocation.
+///
+/// `pids_via_cgroup` should be true when a `CgroupScope` already took
+/// responsibility for `pids_limit` via the `pids` controller; in that case
+/// `RLIMIT_NPROC` is left unset here, since it caps process/thread count
+/// for the whole Termux UID rather than this container's subtree and would
+/// otherwise fail unrelated forks the moment other containers or shells
+/// push the UID's total past the limit.
+pub fn compute_rlimits(limits: &ResourceLimits, pids_via_cgroup: bool) -> Result<ComputedRlimits> {
+    let memory_bytes = limits.memory.as_deref().map(parse_memory_bytes).transpose()?;
+    let pids = if pids_via_cgroup { None } else { limits.pids_limit };
+
+    Ok(ComputedRlimits { memory_bytes, pids })
+}
+
+/// Sets RLIMIT_AS / RLIMIT_NOFILE, and RLIMIT_NPROC as a last-resort
+/// fallback for `pids_limit` when no cgroup scope is available, in the
+/// child right before exec. Runs inside `pre_exec` (post-fork, pre-exec),
+/// so it must stick to async-signal-safe calls only: no heap allocation,
+/// no parsing, no stdio. If a requested limit can't actually be applied,
+/// the child exits immediately rather than silently running unconstrained.
+pub fn apply_rlimits(computed: &ComputedRlimits) -> io::Result<()> {
+    unsafe {
+        if let Some(bytes) = computed.memory_bytes {
+            let rlim = libc::rlimit {
+                rlim_cur: bytes as libc::rlim_t,
+                rlim_max: bytes as libc::rlim_t,
+            };
+            if libc::setrlimit(libc::RLIMIT_AS, &rlim) != 0 {
+                libc::_exit(127);
+            }
+        }
+
+        if let Some(pids) = computed.pids {
+            let rlim = libc::rlimit {
+                rlim_cur: pids as libc::rlim_t,
+                rlim_max: pids as libc::rlim_t,
+            };
+            if libc::setrlimit(libc::RLIMIT_NPROC, &rlim) != 0 {
+                libc::_exit(127);
+            }
+        }
+
+        let nofile = libc::rlimit {
+            rlim_cur: 1024,
+            rlim_max: 1024,
+        };
+        if libc::setrlimit(libc::RLIMIT_NOFILE, &nofile) != 0 {
+            libc::_exit(127);
+        }
+    }
+    Ok(())
+}
+
+fn cgroup_scope_path(container_name: &str) -> PathBuf {
+    Path::new("/sys/fs/cgroup").join("dock").join(container_name)
+}
+
+/// A scoped cgroup v2 subtree for one container's memory/CPU/pids caps,
+/// created only where the hierarchy is actually writable (often not the
+/// case under unprivileged Termux) — conceptually one transient unit per
+/// container, minus systemd.
+pub struct CgroupScope {
+    path: PathBuf,
+}
+
+impl CgroupScope {
+    pub fn create(container_name: &str, limits: &ResourceLimits) -> Option<CgroupScope> {
+        if limits.memory.is_none() && limits.cpus.is_none() && limits.pids_limit.is_none() {
+            return None;
+        }
+
+        let path = cgroup_scope_path(container_name);
+        fs::create_dir_all(&path).ok()?;
+
+        if let Some(memory) = &limits.memory {
+            if let Ok(bytes) = parse_memory_bytes(memory) {
+                let _ = fs::write(path.join("memory.max"), bytes.to_string());
+            }
+        }
+
+        if let Some(cpus) = limits.cpus {
+            let period = 100_000u64;
+            let quota = (cpus * period as f64) as u64;
+            let _ = fs::write(path.join("cpu.max"), format!("{} {}", quota, period));
+        }
+
+        if let Some(pids) = limits.pids_limit {
+            // Unlike RLIMIT_NPROC, this caps only the processes/threads
+            // that land in this container's own cgroup subtree.
+            let _ = fs::write(path.join("pids.max"), pids.to_string());
+        }
+
+        Some(CgroupScope { path })
+    }
+
+    pub fn add_process(&self, pid: u32) -> Result<()> {
+        fs::write(self.path.join("cgroup.procs"), pid.to_string())
+            .map_err(|e| anyhow!("failed to move PID {} into cgroup scope: {}", pid, e))
+    }
+}
+
+/// Tears down a container's cgroup subtree, if one was ever created.
+pub fn remove_scope(container_name: &str) {
+    let _ = fs::remove_dir(cgroup_scope_path(container_name));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_plain_bytes() {
+        assert_eq!(parse_memory_bytes("512").unwrap(), 512);
+    }
+
+    #[test]
+    fn parses_kilobyte_suffix() {
+        assert_eq!(parse_memory_bytes("4k").unwrap(), 4 * 1024);
+    }
+
+    #[test]
+    fn parses_megabyte_suffix() {
+        assert_eq!(parse_memory_bytes("512m").unwrap(), 512 * 1024 * 1024);
+    }
+
+    #[test]
+    fn parses_gigabyte_suffix_case_insensitively() {
+        assert_eq!(parse_memory_bytes("1G").unwrap(), 1024 * 1024 * 1024);
+    }
+
+    #[test]
+    fn rejects_garbage_input() {
+        assert!(parse_memory_bytes("bogus").is_err());
+    }
+
+    #[test]
+    fn compute_rlimits_drops_pids_when_cgroup_owns_them() {
+        let limits = ResourceLimits {
+            memory: None,
+            cpus: None,
+            pids_limit: Some(32),
+        };
+
+        let via_cgroup = compute_rlimits(&limits, true).unwrap();
+        assert_eq!(via_cgroup.pids, None);
+
+        let via_rlimit = compute_rlimits(&limits, false).unwrap();
+        assert_eq!(via_rlimit.pids, Some(32));
+    }
+}
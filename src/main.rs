@@ -0,0 +1,19 @@
+use anyhow::Result;
+use clap::Parser;
+
+mod cli;
+mod compose;
+mod container;
+mod limits;
+mod python;
+mod rootfs;
+mod storage;
+mod volume;
+
+use cli::Cli;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    cli.execute().await
+}
@@ -0,0 +1,89 @@
+use anyhow::{anyhow, Result};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PythonVersion {
+    Python2,
+    Python3,
+}
+
+impl PythonVersion {
+    /// Parses an explicit override (e.g. from a compose manifest), as
+    /// opposed to guessing from a script's shebang.
+    pub fn parse(value: &str) -> Result<PythonVersion> {
+        match value {
+            "python2" | "Python2" => Ok(PythonVersion::Python2),
+            "python3" | "Python3" => Ok(PythonVersion::Python3),
+            other => Err(anyhow!(
+                "invalid python_version '{}' (expected python2 or python3)",
+                other
+            )),
+        }
+    }
+}
+
+/// Inspects a script's shebang line to guess which Python interpreter it needs.
+/// Defaults to Python3 when the shebang is missing or ambiguous.
+pub fn detect_python_version(script: &str) -> Result<PythonVersion> {
+    let file = File::open(script)?;
+    let mut first_line = String::new();
+    BufReader::new(file).read_line(&mut first_line)?;
+
+    if first_line.starts_with("#!") && first_line.contains("python2") {
+        Ok(PythonVersion::Python2)
+    } else {
+        Ok(PythonVersion::Python3)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// Writes `contents` to a uniquely-named scratch file under the OS temp
+    /// dir and returns its path; the caller is responsible for cleanup.
+    fn write_script(name: &str, contents: &str) -> String {
+        let path = std::env::temp_dir().join(format!("dock_test_{}_{}.py", std::process::id(), name));
+        fs::write(&path, contents).unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn parse_accepts_lowercase_and_capitalized_names() {
+        assert_eq!(PythonVersion::parse("python2").unwrap(), PythonVersion::Python2);
+        assert_eq!(PythonVersion::parse("Python2").unwrap(), PythonVersion::Python2);
+        assert_eq!(PythonVersion::parse("python3").unwrap(), PythonVersion::Python3);
+        assert_eq!(PythonVersion::parse("Python3").unwrap(), PythonVersion::Python3);
+    }
+
+    #[test]
+    fn parse_rejects_unknown_version() {
+        assert!(PythonVersion::parse("python4").is_err());
+    }
+
+    #[test]
+    fn detects_python2_shebang() {
+        let path = write_script("py2", "#!/usr/bin/env python2\nprint 'hi'\n");
+        let version = detect_python_version(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(version, PythonVersion::Python2);
+    }
+
+    #[test]
+    fn defaults_to_python3_without_matching_shebang() {
+        let path = write_script("py3", "#!/usr/bin/env python3\nprint('hi')\n");
+        let version = detect_python_version(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(version, PythonVersion::Python3);
+    }
+
+    #[test]
+    fn defaults_to_python3_without_any_shebang() {
+        let path = write_script("no_shebang", "print('hi')\n");
+        let version = detect_python_version(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(version, PythonVersion::Python3);
+    }
+}
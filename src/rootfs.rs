@@ -0,0 +1,142 @@
+use anyhow::{anyhow, Result};
+use flate2::read::GzDecoder;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tar::Archive;
+
+/// A base rootfs that `dock create` can materialize into a container's filesystem.
+pub struct ResolvedImage {
+    pub name: String,
+    pub digest: String,
+}
+
+/// URL for each named base image we know how to fetch. Mirrors the small,
+/// well-known set of minirootfs tarballs Termux users actually want.
+fn image_url(image: &str) -> Result<&'static str> {
+    match image {
+        "alpine" => Ok("https://dl-cdn.alpinelinux.org/alpine/latest-stable/releases/x86_64/alpine-minirootfs-latest.tar.gz"),
+        "python3" => Ok("https://github.com/termux/proot-distro/releases/latest/download/alpine-python3-rootfs.tar.gz"),
+        other => Err(anyhow!(
+            "unknown image '{}' (known images: alpine, python3)",
+            other
+        )),
+    }
+}
+
+/// Cache of extracted rootfs images under `~/.dock/images/<name>/`, with the
+/// downloaded tarball's sha256 kept in `digest.txt`. Entries are keyed by image
+/// name, not by digest, so re-pulling an image replaces its previous contents.
+pub struct RootfsStore {
+    images_dir: PathBuf,
+}
+
+impl RootfsStore {
+    pub fn new() -> Result<Self> {
+        let images_dir = dirs::home_dir()
+            .ok_or_else(|| anyhow!("Could not determine home directory"))?
+            .join(".dock")
+            .join("images");
+        fs::create_dir_all(&images_dir)?;
+        Ok(RootfsStore { images_dir })
+    }
+
+    fn image_dir(&self, image: &str) -> PathBuf {
+        self.images_dir.join(image)
+    }
+
+    fn digest_path(&self, image: &str) -> PathBuf {
+        self.image_dir(image).join("digest.txt")
+    }
+
+    fn rootfs_path(&self, image: &str) -> PathBuf {
+        self.image_dir(image).join("rootfs")
+    }
+
+    /// True once `image` has been downloaded and extracted at least once.
+    pub fn is_cached(&self, image: &str) -> bool {
+        self.rootfs_path(image).exists()
+    }
+
+    /// Downloads and extracts `image` into the cache, unless it's already
+    /// cached and `force` is false (mirrors `docker --pull=always`).
+    pub fn pull(&self, image: &str, force: bool) -> Result<ResolvedImage> {
+        if self.is_cached(image) && !force {
+            let digest = fs::read_to_string(self.digest_path(image))?;
+            return Ok(ResolvedImage {
+                name: image.to_string(),
+                digest,
+            });
+        }
+
+        let url = image_url(image)?;
+        let response = ureq::get(url)
+            .call()
+            .map_err(|e| anyhow!("failed to fetch image '{}' from {}: {}", image, url, e))?;
+
+        let mut bytes = Vec::new();
+        response
+            .into_reader()
+            .read_to_end(&mut bytes)
+            .map_err(|e| anyhow!("failed to read image '{}': {}", image, e))?;
+
+        let digest = format!("sha256:{:x}", Sha256::digest(&bytes));
+
+        let rootfs_path = self.rootfs_path(image);
+        if rootfs_path.exists() {
+            fs::remove_dir_all(&rootfs_path)?;
+        }
+        fs::create_dir_all(&rootfs_path)?;
+
+        let mut archive = Archive::new(GzDecoder::new(bytes.as_slice()));
+        archive
+            .unpack(&rootfs_path)
+            .map_err(|e| anyhow!("failed to extract image '{}': {}", image, e))?;
+
+        fs::write(self.digest_path(image), &digest)?;
+
+        Ok(ResolvedImage {
+            name: image.to_string(),
+            digest,
+        })
+    }
+
+    /// Copy-on-extract: materializes a cached image into a container's own
+    /// filesystem directory so each container gets an independent copy.
+    pub fn materialize(&self, image: &str, target: &Path) -> Result<()> {
+        let src = self.rootfs_path(image);
+        if !src.exists() {
+            return Err(anyhow!(
+                "image '{}' has not been pulled; run `dock pull {}`",
+                image,
+                image
+            ));
+        }
+
+        if target.exists() {
+            fs::remove_dir_all(target)?;
+        }
+        copy_dir_recursive(&src, target)
+    }
+}
+
+fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
+    fs::create_dir_all(dst)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let src_path = entry.path();
+        let dst_path = dst.join(entry.file_name());
+        let file_type = entry.file_type()?;
+
+        if file_type.is_symlink() {
+            let link_target = fs::read_link(&src_path)?;
+            std::os::unix::fs::symlink(&link_target, &dst_path)?;
+        } else if file_type.is_dir() {
+            copy_dir_recursive(&src_path, &dst_path)?;
+        } else {
+            fs::copy(&src_path, &dst_path)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::limits::ResourceLimits;
+use crate::volume::VolumeMount;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContainerConfig {
+    pub id: String,
+    pub name: String,
+    pub script: String,
+    pub python_version: String,
+    pub status: String,
+    pub port_mapping: Option<String>,
+    pub image: String,
+    pub image_digest: Option<String>,
+    pub pid: Option<u32>,
+    pub exit_code: Option<i32>,
+    #[serde(default)]
+    pub volumes: Vec<VolumeMount>,
+    #[serde(default)]
+    pub env: Vec<(String, String)>,
+    #[serde(default)]
+    pub limits: ResourceLimits,
+}
+
+pub struct Storage {
+    base_dir: PathBuf,
+}
+
+impl Storage {
+    pub fn new() -> Result<Self> {
+        let base_dir = dirs::home_dir()
+            .ok_or_else(|| anyhow!("Could not determine home directory"))?
+            .join(".dock");
+
+        fs::create_dir_all(base_dir.join("containers"))?;
+
+        Ok(Storage { base_dir })
+    }
+
+    /// Like `new`, but rooted at an arbitrary directory instead of
+    /// `~/.dock` — lets tests exercise `Storage` without touching the
+    /// real user's container state.
+    #[cfg(test)]
+    pub(crate) fn with_base_dir(base_dir: PathBuf) -> Result<Self> {
+        fs::create_dir_all(base_dir.join("containers"))?;
+        Ok(Storage { base_dir })
+    }
+
+    fn container_dir(&self, name: &str) -> PathBuf {
+        self.base_dir.join("containers").join(name)
+    }
+
+    fn config_path(&self, name: &str) -> PathBuf {
+        self.container_dir(name).join("config.json")
+    }
+
+    pub fn filesystem_path(&self, name: &str) -> PathBuf {
+        self.container_dir(name).join("rootfs")
+    }
+
+    pub fn logs_path(&self, name: &str) -> PathBuf {
+        self.container_dir(name).join("logs.txt")
+    }
+
+    pub fn exit_code_path(&self, name: &str) -> PathBuf {
+        self.container_dir(name).join("exit_code")
+    }
+
+    pub fn container_exists(&self, name: &str) -> bool {
+        self.config_path(name).exists()
+    }
+
+    pub fn save_config(&self, config: &ContainerConfig) -> Result<()> {
+        fs::create_dir_all(self.container_dir(&config.name))?;
+        let json = serde_json::to_string_pretty(config)?;
+        fs::write(self.config_path(&config.name), json)?;
+        Ok(())
+    }
+
+    pub fn load_config(&self, name: &str) -> Result<ContainerConfig> {
+        let path = self.config_path(name);
+        if !path.exists() {
+            return Err(anyhow!("Container '{}' not found", name));
+        }
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    pub fn list_containers(&self) -> Result<Vec<ContainerConfig>> {
+        let containers_dir = self.base_dir.join("containers");
+        let mut configs = Vec::new();
+
+        if !containers_dir.exists() {
+            return Ok(configs);
+        }
+
+        for entry in fs::read_dir(containers_dir)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().to_string();
+            if let Ok(config) = self.load_config(&name) {
+                configs.push(config);
+            }
+        }
+
+        Ok(configs)
+    }
+
+    pub fn delete_container(&self, name: &str) -> Result<()> {
+        let dir = self.container_dir(name);
+        if dir.exists() {
+            fs::remove_dir_all(dir)?;
+        }
+        Ok(())
+    }
+}
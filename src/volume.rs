@@ -0,0 +1,143 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::storage::Storage;
+
+/// A volume attached to a container, translated into a `proot -b` bind at start.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolumeMount {
+    pub name: String,
+    pub target: String,
+}
+
+impl VolumeMount {
+    /// Parses the `--volume name:/path` flag format.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let (name, target) = spec
+            .split_once(':')
+            .ok_or_else(|| anyhow!("invalid volume spec '{}', expected name:/path", spec))?;
+        Ok(VolumeMount {
+            name: name.to_string(),
+            target: target.to_string(),
+        })
+    }
+}
+
+/// Content of named, persistent volumes under `~/.dock/volumes/<name>/data`.
+pub struct VolumeStore {
+    volumes_dir: PathBuf,
+}
+
+impl VolumeStore {
+    pub fn new() -> Result<Self> {
+        let volumes_dir = dirs::home_dir()
+            .ok_or_else(|| anyhow!("Could not determine home directory"))?
+            .join(".dock")
+            .join("volumes");
+        fs::create_dir_all(&volumes_dir)?;
+        Ok(VolumeStore { volumes_dir })
+    }
+
+    fn volume_dir(&self, name: &str) -> PathBuf {
+        self.volumes_dir.join(name)
+    }
+
+    pub fn data_path(&self, name: &str) -> PathBuf {
+        self.volume_dir(name).join("data")
+    }
+
+    pub fn exists(&self, name: &str) -> bool {
+        self.volume_dir(name).exists()
+    }
+
+    pub fn create(&self, name: &str) -> Result<()> {
+        if self.exists(name) {
+            return Err(anyhow!("Volume '{}' already exists", name));
+        }
+        fs::create_dir_all(self.data_path(name))?;
+        Ok(())
+    }
+
+    pub fn list(&self) -> Result<Vec<String>> {
+        let mut names = Vec::new();
+        if !self.volumes_dir.exists() {
+            return Ok(names);
+        }
+        for entry in fs::read_dir(&self.volumes_dir)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                names.push(entry.file_name().to_string_lossy().to_string());
+            }
+        }
+        Ok(names)
+    }
+
+    pub fn remove(&self, name: &str) -> Result<()> {
+        if !self.exists(name) {
+            return Err(anyhow!("Volume '{}' not found", name));
+        }
+        fs::remove_dir_all(self.volume_dir(name))?;
+        Ok(())
+    }
+}
+
+pub struct VolumeManager {
+    store: VolumeStore,
+}
+
+impl VolumeManager {
+    pub fn new() -> Result<Self> {
+        Ok(VolumeManager {
+            store: VolumeStore::new()?,
+        })
+    }
+
+    pub async fn create(&self, name: &str) -> Result<()> {
+        self.store.create(name)?;
+        println!("✓ Volume '{}' created", name);
+        Ok(())
+    }
+
+    pub async fn list(&self) -> Result<()> {
+        let names = self.store.list()?;
+        if names.is_empty() {
+            println!("No volumes found");
+            return Ok(());
+        }
+        println!("{:<20}", "NAME");
+        println!("{}", "-".repeat(20));
+        for name in names {
+            println!("{:<20}", name);
+        }
+        Ok(())
+    }
+
+    pub async fn remove(&self, name: &str) -> Result<()> {
+        self.store.remove(name)?;
+        println!("✓ Volume '{}' removed", name);
+        Ok(())
+    }
+
+    /// Deletes every volume not referenced by any container's `volumes` list.
+    pub async fn prune(&self, storage: &Storage) -> Result<()> {
+        let referenced: HashSet<String> = storage
+            .list_containers()?
+            .into_iter()
+            .flat_map(|config| config.volumes.into_iter().map(|v| v.name))
+            .collect();
+
+        let mut pruned = 0;
+        for name in self.store.list()? {
+            if !referenced.contains(&name) {
+                self.store.remove(&name)?;
+                pruned += 1;
+            }
+        }
+
+        println!("✓ Pruned {} unused volume(s)", pruned);
+        Ok(())
+    }
+}